@@ -0,0 +1,178 @@
+//! Interactive LXC console support: opens a termproxy session, bridges the
+//! resulting `vncwebsocket` binary frames to the frontend via Tauri events,
+//! and accepts typed input back from it.
+
+use crate::proxmox::ProxmoxClient;
+use futures_util::{SinkExt, StreamExt};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::Message;
+
+#[derive(Debug, Serialize, Clone)]
+struct ConsoleFrame {
+    session_id: String,
+    data: Vec<u8>,
+}
+
+enum ConsoleCommand {
+    Input(Vec<u8>),
+    Close,
+}
+
+/// Tracks open console sessions so `console_send`/`console_disconnect` can
+/// route to the right websocket without the frontend holding a raw handle.
+#[derive(Default)]
+pub struct ConsoleRegistry {
+    sessions: Arc<Mutex<HashMap<String, mpsc::UnboundedSender<ConsoleCommand>>>>,
+}
+
+pub async fn connect(
+    app: AppHandle,
+    registry: &ConsoleRegistry,
+    client: ProxmoxClient,
+    node: String,
+    vmid: u32,
+) -> Result<String, String> {
+    let termproxy = client.request_termproxy(&node, vmid).await?;
+    let auth_headers = client.ws_auth_headers().await?;
+
+    let url = format!(
+        "wss://{}/api2/json/nodes/{}/lxc/{}/vncwebsocket?port={}&vncticket={}",
+        client.base_url(),
+        node,
+        vmid,
+        termproxy.port,
+        percent_encode(&termproxy.ticket),
+    );
+
+    let mut request = url
+        .into_client_request()
+        .map_err(|e| format!("Invalid console URL: {}", e))?;
+    for (name, value) in auth_headers {
+        let header_name = reqwest::header::HeaderName::from_bytes(name.as_bytes())
+            .map_err(|e| format!("Invalid auth header name: {}", e))?;
+        let header_value =
+            HeaderValue::from_str(&value).map_err(|e| format!("Invalid auth header value: {}", e))?;
+        request.headers_mut().insert(header_name, header_value);
+    }
+
+    let (ws_stream, _) = tokio_tungstenite::connect_async(request)
+        .await
+        .map_err(|e| format!("Failed to open console websocket: {}", e))?;
+
+    let (mut write, mut read) = ws_stream.split();
+
+    // The `vncticket` query param only authorizes the websocket upgrade at
+    // the API layer; the termproxy process behind it still expects its own
+    // `user:ticket\n` handshake as the first frame before it'll spawn a shell.
+    write
+        .send(Message::Binary(
+            format!("{}:{}\n", termproxy.user, termproxy.ticket).into_bytes(),
+        ))
+        .await
+        .map_err(|e| format!("Failed to authenticate console session: {}", e))?;
+
+    let (tx, mut rx) = mpsc::unbounded_channel::<ConsoleCommand>();
+
+    let session_id = format!("{}-{}-{}", node, vmid, &termproxy.ticket[termproxy.ticket.len().saturating_sub(8)..]);
+    registry.sessions.lock().unwrap().insert(session_id.clone(), tx);
+
+    let task_session_id = session_id.clone();
+    let task_sessions = registry.sessions.clone();
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                incoming = read.next() => {
+                    match incoming {
+                        Some(Ok(Message::Binary(bytes))) => {
+                            let _ = app.emit(
+                                "console://data",
+                                ConsoleFrame { session_id: task_session_id.clone(), data: bytes },
+                            );
+                        }
+                        Some(Ok(Message::Ping(payload))) => {
+                            // Answer termproxy's keepalive ourselves: we own
+                            // the write half, tungstenite won't do it for us.
+                            if write.send(Message::Pong(payload)).await.is_err() {
+                                break;
+                            }
+                        }
+                        Some(Ok(Message::Close(_))) | None => break,
+                        Some(Ok(_)) => {}
+                        Some(Err(_)) => break,
+                    }
+                }
+                cmd = rx.recv() => {
+                    match cmd {
+                        Some(ConsoleCommand::Input(bytes)) => {
+                            if write.send(Message::Binary(bytes)).await.is_err() {
+                                break;
+                            }
+                        }
+                        Some(ConsoleCommand::Close) | None => {
+                            let _ = write.send(Message::Close(None)).await;
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        task_sessions.lock().unwrap().remove(&task_session_id);
+        let _ = app.emit("console://closed", task_session_id);
+    });
+
+    Ok(session_id)
+}
+
+pub fn send_input(registry: &ConsoleRegistry, session_id: &str, data: Vec<u8>) -> Result<(), String> {
+    let sessions = registry.sessions.lock().unwrap();
+    match sessions.get(session_id) {
+        Some(tx) => tx
+            .send(ConsoleCommand::Input(data))
+            .map_err(|_| "Console session already closed".to_string()),
+        None => Err("Unknown console session".to_string()),
+    }
+}
+
+/// Closes a session from our side; the websocket's read loop tears itself
+/// down once it sees the channel drop or the `Close` command.
+pub fn disconnect(registry: &ConsoleRegistry, session_id: &str) {
+    if let Some(tx) = registry.sessions.lock().unwrap().remove(session_id) {
+        let _ = tx.send(ConsoleCommand::Close);
+    }
+}
+
+/// Minimal percent-encoding for a ticket used as a query parameter; PVE
+/// tickets contain `:`, `!` and other reserved characters.
+fn percent_encode(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    for byte in raw.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percent_encode_passes_through_unreserved_chars() {
+        assert_eq!(percent_encode("Az09-_.~"), "Az09-_.~");
+    }
+
+    #[test]
+    fn percent_encode_escapes_reserved_chars() {
+        assert_eq!(percent_encode("PVE:ticket!"), "PVE%3Aticket%21");
+    }
+}
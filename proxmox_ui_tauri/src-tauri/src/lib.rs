@@ -1,27 +1,50 @@
+// NOTE: this crate's Cargo.toml is not part of this source tree, so it
+// can't be amended here. For this module tree to build, it must declare:
+//   - reqwest with the `rustls-tls` (not `native-tls`) and `json` features,
+//     since `use_preconfigured_tls` only exists on the rustls backend
+//   - rustls, plus its `ring` crypto provider, matching the provider
+//     `ProxmoxClient::new`'s fingerprint-pinning branch constructs directly
+//   - ring itself, for the constant-time fingerprint comparison
+//   - sha2, for hashing the pinned certificate
+//   - tokio with the `macros`, `rt`, `sync` and `time` features, which the
+//     console bridge and task polling use directly
+//   - tokio-tungstenite and futures-util, for the console websocket bridge;
+//     `console.rs` assumes a tungstenite version whose `Message::Binary`
+//     carries a `Vec<u8>` payload (older/newer releases use `bytes::Bytes`,
+//     which would need the call sites in `console.rs` updated to match)
+mod console;
 mod proxmox;
 
-use proxmox::{ProxmoxClient, Container};
+use console::ConsoleRegistry;
+use proxmox::{Container, NodeInfo, ProxmoxClient, RrdSample, RrdTimeframe, TaskStatus, TlsMode};
 use serde::Serialize;
 use std::sync::Mutex;
-use tauri::State;
+use tauri::{AppHandle, State};
 
 #[derive(Serialize)]
 struct ProxmoxConfig {
     host: String,
-    node: String,
+    /// Legacy single-node hint from `PROXMOX_NODE`, if set. Containers now
+    /// carry their own owning node, so this is informational only.
+    node: Option<String>,
+    /// `None` until a `ProxmoxClient` has been successfully initialized.
+    tls_mode: Option<TlsMode>,
 }
 
 // Global state for Proxmox client
 struct AppState {
     proxmox: Mutex<Option<ProxmoxClient>>,
     config: ProxmoxConfig,
+    console: ConsoleRegistry,
 }
 
 #[tauri::command]
 fn get_config(state: State<'_, AppState>) -> ProxmoxConfig {
+    let tls_mode = state.proxmox.lock().unwrap().as_ref().map(|c| c.tls_mode());
     ProxmoxConfig {
         host: state.config.host.clone(),
         node: state.config.node.clone(),
+        tls_mode,
     }
 }
 
@@ -39,54 +62,145 @@ async fn get_containers(state: State<'_, AppState>) -> Result<Vec<Container>, St
 }
 
 #[tauri::command]
-async fn start_container(vmid: u32, state: State<'_, AppState>) -> Result<String, String> {
+async fn login(
+    username: String,
+    password: String,
+    realm: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
     let client = {
         let guard = state.proxmox.lock().unwrap();
         guard.clone()
     };
-    
+
     match client {
-        Some(proxmox) => proxmox.start_container(vmid).await,
+        Some(proxmox) => proxmox.login(&username, &password, &realm).await,
         None => Err("Proxmox client not initialized".to_string()),
     }
 }
 
 #[tauri::command]
-async fn stop_container(vmid: u32, state: State<'_, AppState>) -> Result<String, String> {
+async fn list_nodes(state: State<'_, AppState>) -> Result<Vec<NodeInfo>, String> {
     let client = {
         let guard = state.proxmox.lock().unwrap();
         guard.clone()
     };
-    
+
     match client {
-        Some(proxmox) => proxmox.stop_container(vmid).await,
+        Some(proxmox) => proxmox.get_nodes().await,
         None => Err("Proxmox client not initialized".to_string()),
     }
 }
 
 #[tauri::command]
-async fn delete_container(vmid: u32, state: State<'_, AppState>) -> Result<String, String> {
+async fn get_container_rrd(
+    node: String,
+    vmid: u32,
+    timeframe: RrdTimeframe,
+    state: State<'_, AppState>,
+) -> Result<Vec<RrdSample>, String> {
     let client = {
         let guard = state.proxmox.lock().unwrap();
         guard.clone()
     };
-    
+
+    match client {
+        Some(proxmox) => proxmox.get_container_rrd(&node, vmid, timeframe).await,
+        None => Err("Proxmox client not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+async fn start_container(node: String, vmid: u32, state: State<'_, AppState>) -> Result<String, String> {
+    let client = {
+        let guard = state.proxmox.lock().unwrap();
+        guard.clone()
+    };
+
+    match client {
+        Some(proxmox) => proxmox.start_container(&node, vmid).await,
+        None => Err("Proxmox client not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+async fn stop_container(node: String, vmid: u32, state: State<'_, AppState>) -> Result<String, String> {
+    let client = {
+        let guard = state.proxmox.lock().unwrap();
+        guard.clone()
+    };
+
+    match client {
+        Some(proxmox) => proxmox.stop_container(&node, vmid).await,
+        None => Err("Proxmox client not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+async fn delete_container(node: String, vmid: u32, state: State<'_, AppState>) -> Result<String, String> {
+    let client = {
+        let guard = state.proxmox.lock().unwrap();
+        guard.clone()
+    };
+
+    match client {
+        Some(proxmox) => proxmox.delete_container(&node, vmid).await,
+        None => Err("Proxmox client not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+async fn wait_for_task(upid: String, state: State<'_, AppState>) -> Result<TaskStatus, String> {
+    let client = {
+        let guard = state.proxmox.lock().unwrap();
+        guard.clone()
+    };
+
+    match client {
+        Some(proxmox) => proxmox.get_task_status(&upid).await,
+        None => Err("Proxmox client not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+async fn console_connect(
+    node: String,
+    vmid: u32,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let client = {
+        let guard = state.proxmox.lock().unwrap();
+        guard.clone()
+    };
+
     match client {
-        Some(proxmox) => proxmox.delete_container(vmid).await,
+        Some(proxmox) => console::connect(app, &state.console, proxmox, node, vmid).await,
         None => Err("Proxmox client not initialized".to_string()),
     }
 }
 
+#[tauri::command]
+fn console_send(session_id: String, data: Vec<u8>, state: State<'_, AppState>) -> Result<(), String> {
+    console::send_input(&state.console, &session_id, data)
+}
+
+#[tauri::command]
+fn console_disconnect(session_id: String, state: State<'_, AppState>) {
+    console::disconnect(&state.console, &session_id);
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     // Load config from environment
     dotenv::dotenv().ok();
     let host = std::env::var("PROXMOX_HOST").unwrap_or_else(|_| "unknown".to_string());
-    let node = std::env::var("PROXMOX_NODE").unwrap_or_else(|_| "unknown".to_string());
-    
+    let node = std::env::var("PROXMOX_NODE").ok();
+
     let config = ProxmoxConfig {
         host: host.clone(),
-        node: node.clone(),
+        node,
+        tls_mode: None,
     };
 
     // Initialize Proxmox client
@@ -107,13 +221,21 @@ pub fn run() {
         .manage(AppState {
             proxmox: Mutex::new(proxmox_client),
             config,
+            console: ConsoleRegistry::default(),
         })
         .invoke_handler(tauri::generate_handler![
             get_config,
+            login,
+            list_nodes,
             get_containers,
+            get_container_rrd,
             start_container,
             stop_container,
-            delete_container
+            delete_container,
+            wait_for_task,
+            console_connect,
+            console_send,
+            console_disconnect
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
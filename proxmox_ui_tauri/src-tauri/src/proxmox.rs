@@ -1,19 +1,203 @@
-use reqwest::Client;
+use reqwest::{Client, Method};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{DigitallySignedStruct, Error as TlsError, SignatureScheme};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// How long a PVE login ticket is valid for before the server rejects it.
+const TICKET_LIFETIME: Duration = Duration::from_secs(2 * 60 * 60);
+/// Refresh this long before the real expiry so a request never races it.
+const TICKET_REFRESH_MARGIN: Duration = Duration::from_secs(5 * 60);
+/// Generous default so a stalled PVE node can't hang a Tauri command forever.
+const DEFAULT_TIMEOUT_SECS: u64 = 120;
+/// Upper bound on how long `get_task_status` will poll a single task before
+/// giving up, so a stuck worker can't hang `wait_for_task` indefinitely.
+const MAX_TASK_WAIT: Duration = Duration::from_secs(30 * 60);
+
+/// Applies the request timeout and outbound proxy settings shared by every
+/// TLS mode, so a hung node or a corporate proxy don't need separate
+/// handling per branch in [`ProxmoxClient::new`].
+fn configured_builder() -> Result<reqwest::ClientBuilder, String> {
+    let timeout_secs = env::var("PROXMOX_TIMEOUT")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_TIMEOUT_SECS);
+
+    let mut builder = Client::builder().timeout(Duration::from_secs(timeout_secs));
+
+    if let Some(proxy_url) = env::var("PROXMOX_PROXY").ok().or_else(|| env::var("HTTPS_PROXY").ok()) {
+        let mut proxy = reqwest::Proxy::all(&proxy_url)
+            .map_err(|e| format!("Invalid PROXMOX_PROXY/HTTPS_PROXY URL: {}", e))?;
+
+        if let (Ok(username), Ok(password)) = (
+            env::var("PROXMOX_PROXY_USERNAME"),
+            env::var("PROXMOX_PROXY_PASSWORD"),
+        ) {
+            proxy = proxy.basic_auth(&username, &password);
+        }
+
+        builder = builder.proxy(proxy);
+    }
+
+    Ok(builder)
+}
+
+/// How the active connection is validating the PVE node's TLS certificate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TlsMode {
+    /// Peer certificate is checked against the platform's normal CA roots.
+    CaVerified,
+    /// Peer certificate is checked only against a pinned SHA-256 fingerprint.
+    FingerprintPinned,
+    /// No certificate validation at all. Only used when explicitly opted in.
+    Insecure,
+}
+
+const DEFAULT_PORT: u16 = 8006;
 
 #[derive(Debug, Clone)]
 pub struct ProxmoxClient {
     client: Client,
     host: String,
-    node: String,
-    token_id: String,
-    token_secret: String,
+    port: u16,
+    credential: Arc<Mutex<Credential>>,
+    tls_mode: TlsMode,
+}
+
+/// The two ways this client can authenticate against the PVE API, mirroring
+/// the Proxmox client's `AuthInfo { ticket, token }` split.
+#[derive(Debug, Clone)]
+enum Credential {
+    /// A preconfigured API token (`user@realm!tokenid=secret`).
+    Token { token_id: String, token_secret: String },
+    /// A ticket obtained via `login`, refreshed shortly before it expires.
+    Ticket {
+        username: String,
+        realm: String,
+        ticket: String,
+        csrf: String,
+        expires: SystemTime,
+    },
+    /// No credential configured yet; every request fails until `login` runs.
+    Unauthenticated,
+}
+
+#[derive(Debug, Deserialize)]
+struct TicketData {
+    ticket: String,
+    #[serde(rename = "CSRFPreventionToken")]
+    csrf_prevention_token: String,
+}
+
+/// Splits a `PROXMOX_HOST` value into a bare hostname and port (e.g. a
+/// non-default port behind a reverse proxy), defaulting to the standard PVE
+/// API port when none is given. The one port is used for every node.
+fn parse_host_port(raw: &str) -> (String, u16) {
+    match raw.rsplit_once(':') {
+        Some((host, port)) => match port.parse::<u16>() {
+            Ok(port) => (host.to_string(), port),
+            Err(_) => (raw.to_string(), DEFAULT_PORT),
+        },
+        None => (raw.to_string(), DEFAULT_PORT),
+    }
+}
+
+/// Verifies the peer certificate by comparing its SHA-256 digest against a
+/// pinned value instead of walking a CA chain, mirroring the
+/// `verify_cert`/`fingerprint` pairing used by the Proxmox backup client.
+#[derive(Debug)]
+struct FingerprintVerifier {
+    pin: [u8; 32],
+}
+
+impl ServerCertVerifier for FingerprintVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, TlsError> {
+        let digest = Sha256::digest(end_entity.as_ref());
+        if ring::constant_time::verify_slices_are_equal(&digest, &self.pin).is_ok() {
+            Ok(ServerCertVerified::assertion())
+        } else {
+            Err(TlsError::General(format!(
+                "certificate fingerprint mismatch: expected {}, got {}",
+                hex_fingerprint(&self.pin),
+                hex_fingerprint(&digest)
+            )))
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, TlsError> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &rustls::crypto::ring::default_provider().signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, TlsError> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &rustls::crypto::ring::default_provider().signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        rustls::crypto::ring::default_provider()
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+fn hex_fingerprint(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>().join(":")
+}
+
+/// Parses a SHA-256 fingerprint that may be plain hex or colon-separated hex
+/// (e.g. `"AA:BB:CC..."`), as PVE prints it in its GUI.
+fn parse_fingerprint(raw: &str) -> Result<[u8; 32], String> {
+    let cleaned: String = raw.chars().filter(|c| *c != ':').collect();
+    if cleaned.len() != 64 {
+        return Err(format!(
+            "PROXMOX_FINGERPRINT must be a 32-byte SHA-256 digest in hex, got {} hex chars",
+            cleaned.len()
+        ));
+    }
+
+    let mut pin = [0u8; 32];
+    for (i, byte) in pin.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&cleaned[i * 2..i * 2 + 2], 16)
+            .map_err(|_| "PROXMOX_FINGERPRINT contains non-hex characters".to_string())?;
+    }
+    Ok(pin)
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Container {
     pub vmid: u32,
+    pub node: String,
     pub name: String,
     pub status: String,
     pub uptime: u64,
@@ -26,8 +210,21 @@ pub struct Container {
     pub ip_address: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NodeInfo {
+    pub node: String,
+    pub status: String,
+    pub cpu: Option<f64>,
+    pub maxcpu: Option<u32>,
+    pub mem: Option<u64>,
+    pub maxmem: Option<u64>,
+}
+
+/// One `lxc`-typed entry from `/cluster/resources`, which is how containers
+/// across every node in the cluster are discovered in a single call.
 #[derive(Debug, Deserialize)]
-struct ProxmoxContainer {
+struct ClusterLxcResource {
+    node: String,
     vmid: u32,
     name: Option<String>,
     status: String,
@@ -35,59 +232,335 @@ struct ProxmoxContainer {
     mem: Option<u64>,
     maxmem: Option<u64>,
     cpu: Option<f64>,
-    cpus: Option<u32>,
+    maxcpu: Option<u32>,
     diskread: Option<u64>,
     diskwrite: Option<u64>,
 }
 
+#[derive(Debug, Deserialize)]
+struct ProxmoxNode {
+    node: String,
+    status: String,
+    cpu: Option<f64>,
+    maxcpu: Option<u32>,
+    mem: Option<u64>,
+    maxmem: Option<u64>,
+}
+
 #[derive(Debug, Deserialize)]
 struct ProxmoxResponse<T> {
     data: T,
 }
 
+/// The terminal status of an asynchronous PVE task (a UPID).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaskStatus {
+    pub upid: String,
+    pub status: String,
+    pub exit_status: Option<String>,
+    /// Last lines of the task log, populated only when `exit_status != "OK"`.
+    pub log_tail: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TaskStatusData {
+    status: String,
+    exitstatus: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TaskLogLine {
+    #[allow(dead_code)]
+    n: u64,
+    t: String,
+}
+
+/// Granularity accepted by PVE's `rrddata` endpoint.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RrdTimeframe {
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl RrdTimeframe {
+    fn as_query_str(&self) -> &'static str {
+        match self {
+            Self::Hour => "hour",
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+            Self::Year => "year",
+        }
+    }
+}
+
+/// One time-bucketed sample from `/lxc/{vmid}/rrddata`, used to draw
+/// historical CPU/memory/disk/network usage graphs.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RrdSample {
+    pub time: u64,
+    pub cpu: Option<f64>,
+    // `cf=AVERAGE` consolidation returns these as floats (e.g. rate DSs like
+    // `netin` are routinely fractional), so `u64` fails to deserialize them.
+    pub mem: Option<f64>,
+    pub maxmem: Option<f64>,
+    pub netin: Option<f64>,
+    pub netout: Option<f64>,
+    pub diskread: Option<f64>,
+    pub diskwrite: Option<f64>,
+}
+
 #[derive(Debug, Deserialize)]
 struct NetworkInterface {
     name: String,
     inet: Option<String>,
 }
 
+/// Response from `/lxc/{vmid}/termproxy`: a one-shot ticket scoped to a
+/// single console session, separate from the main login/token credential.
+#[derive(Debug, Deserialize)]
+pub(crate) struct TermProxyData {
+    pub user: String,
+    pub ticket: String,
+    pub port: String,
+}
+
 impl ProxmoxClient {
     pub fn new() -> Result<Self, String> {
         dotenv::dotenv().ok();
         
-        let host = env::var("PROXMOX_HOST")
+        let raw_host = env::var("PROXMOX_HOST")
             .map_err(|_| "PROXMOX_HOST not set in .env file".to_string())?;
-        let node = env::var("PROXMOX_NODE")
-            .map_err(|_| "PROXMOX_NODE not set in .env file".to_string())?;
-        let token_id = env::var("PROXMOX_TOKEN_ID")
-            .map_err(|_| "PROXMOX_TOKEN_ID not set in .env file".to_string())?;
-        let token_secret = env::var("PROXMOX_TOKEN_SECRET")
-            .map_err(|_| "PROXMOX_TOKEN_SECRET not set in .env file".to_string())?;
-
-        let client = Client::builder()
-            .danger_accept_invalid_certs(true)
-            .build()
-            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+        let (host, port) = parse_host_port(&raw_host);
+
+        // A preconfigured token is optional now: a caller with only a
+        // username/password can authenticate later via `login`.
+        let credential = match (
+            env::var("PROXMOX_TOKEN_ID").ok(),
+            env::var("PROXMOX_TOKEN_SECRET").ok(),
+        ) {
+            (Some(token_id), Some(token_secret)) => Credential::Token { token_id, token_secret },
+            (None, None) => Credential::Unauthenticated,
+            _ => {
+                return Err(
+                    "PROXMOX_TOKEN_ID and PROXMOX_TOKEN_SECRET must both be set, or both omitted"
+                        .to_string(),
+                )
+            }
+        };
+
+        let (client, tls_mode) = match env::var("PROXMOX_FINGERPRINT").ok() {
+            Some(raw) => {
+                let pin = parse_fingerprint(&raw)?;
+                // Build with an explicit provider instead of
+                // `ClientConfig::builder()`, which resolves the
+                // process-default `CryptoProvider` and panics if one was
+                // never installed.
+                let tls_config = rustls::ClientConfig::builder_with_provider(Arc::new(
+                    rustls::crypto::ring::default_provider(),
+                ))
+                .with_safe_default_protocol_versions()
+                .map_err(|e| format!("Failed to configure TLS: {}", e))?
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(FingerprintVerifier { pin }))
+                .with_no_client_auth();
+
+                let client = configured_builder()?
+                    .use_preconfigured_tls(tls_config)
+                    .build()
+                    .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+                (client, TlsMode::FingerprintPinned)
+            }
+            None if env::var("PROXMOX_INSECURE").as_deref() == Ok("true") => {
+                let client = configured_builder()?
+                    .danger_accept_invalid_certs(true)
+                    .build()
+                    .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+                (client, TlsMode::Insecure)
+            }
+            None => {
+                let client = configured_builder()?
+                    .build()
+                    .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+                (client, TlsMode::CaVerified)
+            }
+        };
 
         Ok(ProxmoxClient {
             client,
             host,
-            node,
-            token_id,
-            token_secret,
+            port,
+            credential: Arc::new(Mutex::new(credential)),
+            tls_mode,
         })
     }
 
-    fn auth_header(&self) -> String {
-        format!("PVEAPIToken={}={}", self.token_id, self.token_secret)
+    pub fn tls_mode(&self) -> TlsMode {
+        self.tls_mode
+    }
+
+    pub(crate) fn base_url(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+
+    /// Logs in with a username/password instead of a preconfigured API
+    /// token, storing the resulting ticket for subsequent requests.
+    pub async fn login(&self, username: &str, password: &str, realm: &str) -> Result<(), String> {
+        let (ticket, csrf, expires) = self.request_ticket(username, password, realm).await?;
+
+        let mut credential = self.credential.lock().unwrap();
+        *credential = Credential::Ticket {
+            username: username.to_string(),
+            realm: realm.to_string(),
+            ticket,
+            csrf,
+            expires,
+        };
+        Ok(())
+    }
+
+    async fn request_ticket(
+        &self,
+        username: &str,
+        password: &str,
+        realm: &str,
+    ) -> Result<(String, String, SystemTime), String> {
+        let url = format!("https://{}/api2/json/access/ticket", self.base_url());
+        let full_username = format!("{}@{}", username, realm);
+
+        let response = self
+            .client
+            .post(&url)
+            .form(&[("username", full_username.as_str()), ("password", password)])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach ticket endpoint: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Login failed: {}", response.status()));
+        }
+
+        let body: ProxmoxResponse<TicketData> = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse ticket response: {}", e))?;
+
+        let expires = SystemTime::now() + TICKET_LIFETIME - TICKET_REFRESH_MARGIN;
+        Ok((body.data.ticket, body.data.csrf_prevention_token, expires))
+    }
+
+    /// Renews the stored ticket if it's near expiry, per PVE's convention of
+    /// accepting the current ticket as the `password` on `/access/ticket`.
+    async fn renew_ticket_if_needed(&self) -> Result<(), String> {
+        let stale = {
+            let credential = self.credential.lock().unwrap();
+            match &*credential {
+                Credential::Ticket { expires, .. } => SystemTime::now() >= *expires,
+                _ => false,
+            }
+        };
+        if !stale {
+            return Ok(());
+        }
+
+        let (username, realm, current_ticket) = {
+            let credential = self.credential.lock().unwrap();
+            match &*credential {
+                Credential::Ticket { username, realm, ticket, .. } => {
+                    (username.clone(), realm.clone(), ticket.clone())
+                }
+                _ => return Ok(()),
+            }
+        };
+
+        let (ticket, csrf, expires) = self
+            .request_ticket(&username, &current_ticket, &realm)
+            .await?;
+
+        let mut credential = self.credential.lock().unwrap();
+        *credential = Credential::Ticket { username, realm, ticket, csrf, expires };
+        Ok(())
+    }
+
+    /// Builds a request with whichever credential is active, renewing a
+    /// near-expiry ticket first and attaching the CSRF header that PVE
+    /// requires on state-changing methods when using ticket auth.
+    async fn authed_request(&self, method: Method, url: &str) -> Result<reqwest::RequestBuilder, String> {
+        self.renew_ticket_if_needed().await?;
+
+        let credential = self.credential.lock().unwrap().clone();
+        let builder = self.client.request(method.clone(), url);
+
+        match credential {
+            Credential::Token { token_id, token_secret } => Ok(builder.header(
+                "Authorization",
+                format!("PVEAPIToken={}={}", token_id, token_secret),
+            )),
+            Credential::Ticket { ticket, csrf, .. } => {
+                let builder = builder.header("Cookie", format!("PVEAuthCookie={}", ticket));
+                let builder = if matches!(method, Method::POST | Method::PUT | Method::DELETE) {
+                    builder.header("CSRFPreventionToken", csrf)
+                } else {
+                    builder
+                };
+                Ok(builder)
+            }
+            Credential::Unauthenticated => Err(
+                "Not authenticated: set PROXMOX_TOKEN_ID/PROXMOX_TOKEN_SECRET or call login() first"
+                    .to_string(),
+            ),
+        }
+    }
+
+    pub async fn get_nodes(&self) -> Result<Vec<NodeInfo>, String> {
+        let url = format!("https://{}/api2/json/nodes", self.base_url());
+
+        let response = self
+            .authed_request(Method::GET, &url)
+            .await?
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch nodes: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("API error: {}", response.status()));
+        }
+
+        let proxmox_response: ProxmoxResponse<Vec<ProxmoxNode>> = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+        let mut nodes: Vec<NodeInfo> = proxmox_response
+            .data
+            .into_iter()
+            .map(|n| NodeInfo {
+                node: n.node,
+                status: n.status,
+                cpu: n.cpu,
+                maxcpu: n.maxcpu,
+                mem: n.mem,
+                maxmem: n.maxmem,
+            })
+            .collect();
+
+        nodes.sort_by(|a, b| a.node.cmp(&b.node));
+        Ok(nodes)
     }
 
     pub async fn get_containers(&self) -> Result<Vec<Container>, String> {
-        let url = format!("https://{}/api2/json/nodes/{}/lxc", self.host, self.node);
-        
-        let response = self.client
-            .get(&url)
-            .header("Authorization", self.auth_header())
+        let url = format!(
+            "https://{}/api2/json/cluster/resources?type=lxc",
+            self.base_url()
+        );
+
+        let response = self
+            .authed_request(Method::GET, &url)
+            .await?
             .send()
             .await
             .map_err(|e| format!("Failed to fetch containers: {}", e))?;
@@ -96,7 +569,7 @@ impl ProxmoxClient {
             return Err(format!("API error: {}", response.status()));
         }
 
-        let proxmox_response: ProxmoxResponse<Vec<ProxmoxContainer>> = response
+        let proxmox_response: ProxmoxResponse<Vec<ClusterLxcResource>> = response
             .json()
             .await
             .map_err(|e| format!("Failed to parse response: {}", e))?;
@@ -104,20 +577,21 @@ impl ProxmoxClient {
         let mut containers = Vec::new();
         for ct in proxmox_response.data {
             let ip_address = if ct.status == "running" {
-                self.get_container_ip(ct.vmid).await.ok()
+                self.get_container_ip(&ct.node, ct.vmid).await.ok()
             } else {
                 None
             };
 
             containers.push(Container {
                 vmid: ct.vmid,
+                node: ct.node,
                 name: ct.name.unwrap_or_else(|| format!("CT-{}", ct.vmid)),
                 status: ct.status,
                 uptime: ct.uptime.unwrap_or(0),
                 memory: ct.mem.unwrap_or(0),
                 max_memory: ct.maxmem.unwrap_or(0),
                 cpu: ct.cpu.unwrap_or(0.0),
-                cpus: ct.cpus.unwrap_or(1),
+                cpus: ct.maxcpu.unwrap_or(1),
                 disk_read: ct.diskread.unwrap_or(0),
                 disk_write: ct.diskwrite.unwrap_or(0),
                 ip_address,
@@ -128,15 +602,15 @@ impl ProxmoxClient {
         Ok(containers)
     }
 
-    async fn get_container_ip(&self, vmid: u32) -> Result<String, String> {
+    async fn get_container_ip(&self, node: &str, vmid: u32) -> Result<String, String> {
         let url = format!(
             "https://{}/api2/json/nodes/{}/lxc/{}/interfaces",
-            self.host, self.node, vmid
+            self.base_url(), node, vmid
         );
 
-        let response = self.client
-            .get(&url)
-            .header("Authorization", self.auth_header())
+        let response = self
+            .authed_request(Method::GET, &url)
+            .await?
             .send()
             .await
             .map_err(|e| format!("Failed to fetch IP: {}", e))?;
@@ -162,15 +636,102 @@ impl ProxmoxClient {
         Err("No IP found".to_string())
     }
 
-    pub async fn start_container(&self, vmid: u32) -> Result<String, String> {
+    /// Opens a termproxy session for a container's console, returning the
+    /// one-shot ticket and port the caller must use on `vncwebsocket`.
+    pub(crate) async fn request_termproxy(
+        &self,
+        node: &str,
+        vmid: u32,
+    ) -> Result<TermProxyData, String> {
+        let url = format!(
+            "https://{}/api2/json/nodes/{}/lxc/{}/termproxy",
+            self.base_url(), node, vmid
+        );
+
+        let response = self
+            .authed_request(Method::POST, &url)
+            .await?
+            .send()
+            .await
+            .map_err(|e| format!("Failed to open termproxy: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Failed to open termproxy: {}", response.status()));
+        }
+
+        let body: ProxmoxResponse<TermProxyData> = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse termproxy response: {}", e))?;
+
+        Ok(body.data)
+    }
+
+    /// Raw `(header, value)` pairs for authenticating a websocket upgrade,
+    /// since that handshake can't go through reqwest's `RequestBuilder`.
+    pub(crate) async fn ws_auth_headers(&self) -> Result<Vec<(String, String)>, String> {
+        self.renew_ticket_if_needed().await?;
+
+        let credential = self.credential.lock().unwrap().clone();
+        match credential {
+            Credential::Token { token_id, token_secret } => Ok(vec![(
+                "Authorization".to_string(),
+                format!("PVEAPIToken={}={}", token_id, token_secret),
+            )]),
+            Credential::Ticket { ticket, .. } => {
+                Ok(vec![("Cookie".to_string(), format!("PVEAuthCookie={}", ticket))])
+            }
+            Credential::Unauthenticated => Err(
+                "Not authenticated: set PROXMOX_TOKEN_ID/PROXMOX_TOKEN_SECRET or call login() first"
+                    .to_string(),
+            ),
+        }
+    }
+
+    /// Fetches historical CPU/memory/disk/network samples for a container,
+    /// mirroring how proxmox-backup surfaces node/guest rrd series.
+    pub async fn get_container_rrd(
+        &self,
+        node: &str,
+        vmid: u32,
+        timeframe: RrdTimeframe,
+    ) -> Result<Vec<RrdSample>, String> {
+        let url = format!(
+            "https://{}/api2/json/nodes/{}/lxc/{}/rrddata?timeframe={}&cf=AVERAGE",
+            self.base_url(), node, vmid, timeframe.as_query_str()
+        );
+
+        let response = self
+            .authed_request(Method::GET, &url)
+            .await?
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch rrd data: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Failed to fetch rrd data: {}", response.status()));
+        }
+
+        let body: ProxmoxResponse<Vec<RrdSample>> = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse rrd data: {}", e))?;
+
+        Ok(body.data)
+    }
+
+    /// Starts a container and returns the UPID of the task PVE spawned for
+    /// it; the operation is asynchronous on the PVE side, so a success
+    /// response here only means the task was accepted, not finished.
+    pub async fn start_container(&self, node: &str, vmid: u32) -> Result<String, String> {
         let url = format!(
             "https://{}/api2/json/nodes/{}/lxc/{}/status/start",
-            self.host, self.node, vmid
+            self.base_url(), node, vmid
         );
 
-        let response = self.client
-            .post(&url)
-            .header("Authorization", self.auth_header())
+        let response = self
+            .authed_request(Method::POST, &url)
+            .await?
             .send()
             .await
             .map_err(|e| format!("Failed to start container: {}", e))?;
@@ -179,18 +740,20 @@ impl ProxmoxClient {
             return Err(format!("Failed to start container: {}", response.status()));
         }
 
-        Ok(format!("Container {} started successfully", vmid))
+        parse_upid(response).await
     }
 
-    pub async fn stop_container(&self, vmid: u32) -> Result<String, String> {
+    /// Stops a container and returns the UPID of the resulting task. See
+    /// [`ProxmoxClient::start_container`].
+    pub async fn stop_container(&self, node: &str, vmid: u32) -> Result<String, String> {
         let url = format!(
             "https://{}/api2/json/nodes/{}/lxc/{}/status/stop",
-            self.host, self.node, vmid
+            self.base_url(), node, vmid
         );
 
-        let response = self.client
-            .post(&url)
-            .header("Authorization", self.auth_header())
+        let response = self
+            .authed_request(Method::POST, &url)
+            .await?
             .send()
             .await
             .map_err(|e| format!("Failed to stop container: {}", e))?;
@@ -199,18 +762,20 @@ impl ProxmoxClient {
             return Err(format!("Failed to stop container: {}", response.status()));
         }
 
-        Ok(format!("Container {} stopped successfully", vmid))
+        parse_upid(response).await
     }
 
-    pub async fn delete_container(&self, vmid: u32) -> Result<String, String> {
+    /// Deletes a container and returns the UPID of the resulting task. See
+    /// [`ProxmoxClient::start_container`].
+    pub async fn delete_container(&self, node: &str, vmid: u32) -> Result<String, String> {
         let url = format!(
             "https://{}/api2/json/nodes/{}/lxc/{}",
-            self.host, self.node, vmid
+            self.base_url(), node, vmid
         );
 
-        let response = self.client
-            .delete(&url)
-            .header("Authorization", self.auth_header())
+        let response = self
+            .authed_request(Method::DELETE, &url)
+            .await?
             .send()
             .await
             .map_err(|e| format!("Failed to delete container: {}", e))?;
@@ -219,6 +784,174 @@ impl ProxmoxClient {
             return Err(format!("Failed to delete container: {}", response.status()));
         }
 
-        Ok(format!("Container {} deleted successfully", vmid))
+        parse_upid(response).await
+    }
+
+    /// Polls a task's status until it finishes, following the
+    /// WorkerTask/task-log pattern proxmox-backup uses to surface progress.
+    /// On failure, also reads the last lines of the task log to explain why.
+    pub async fn get_task_status(&self, upid: &str) -> Result<TaskStatus, String> {
+        let node = parse_upid_node(upid)?;
+        let started = std::time::Instant::now();
+
+        loop {
+            if started.elapsed() >= MAX_TASK_WAIT {
+                return Err(format!(
+                    "Timed out after {}s waiting for task {} to finish",
+                    MAX_TASK_WAIT.as_secs(),
+                    upid
+                ));
+            }
+
+            let url = format!(
+                "https://{}/api2/json/nodes/{}/tasks/{}/status",
+                self.base_url(), node, upid
+            );
+
+            let response = self
+                .authed_request(Method::GET, &url)
+                .await?
+                .send()
+                .await
+                .map_err(|e| format!("Failed to fetch task status: {}", e))?;
+
+            if !response.status().is_success() {
+                return Err(format!("Failed to fetch task status: {}", response.status()));
+            }
+
+            let body: ProxmoxResponse<TaskStatusData> = response
+                .json()
+                .await
+                .map_err(|e| format!("Failed to parse task status: {}", e))?;
+
+            if body.data.status == "stopped" {
+                let log_tail = if body.data.exitstatus.as_deref() != Some("OK") {
+                    self.get_task_log_tail(&node, upid, 20).await.ok()
+                } else {
+                    None
+                };
+
+                return Ok(TaskStatus {
+                    upid: upid.to_string(),
+                    status: body.data.status,
+                    exit_status: body.data.exitstatus,
+                    log_tail,
+                });
+            }
+
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+    }
+
+    async fn get_task_log_tail(
+        &self,
+        node: &str,
+        upid: &str,
+        lines: usize,
+    ) -> Result<Vec<String>, String> {
+        let url = format!(
+            "https://{}/api2/json/nodes/{}/tasks/{}/log",
+            self.base_url(), node, upid
+        );
+
+        let response = self
+            .authed_request(Method::GET, &url)
+            .await?
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch task log: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Failed to fetch task log: {}", response.status()));
+        }
+
+        let body: ProxmoxResponse<Vec<TaskLogLine>> = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse task log: {}", e))?;
+
+        let skip = body.data.len().saturating_sub(lines);
+        Ok(body.data.into_iter().skip(skip).map(|l| l.t).collect())
+    }
+}
+
+async fn parse_upid(response: reqwest::Response) -> Result<String, String> {
+    let body: ProxmoxResponse<String> = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse task id: {}", e))?;
+    Ok(body.data)
+}
+
+/// UPIDs are formatted as `UPID:<node>:<pid>:<pstart>:<starttime>:<type>:<id>:<user>:`.
+fn parse_upid_node(upid: &str) -> Result<String, String> {
+    upid.split(':')
+        .nth(1)
+        .map(|s| s.to_string())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| format!("Malformed UPID: {}", upid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_host_port_splits_explicit_port() {
+        assert_eq!(parse_host_port("pve.example.com:8443"), ("pve.example.com".to_string(), 8443));
+    }
+
+    #[test]
+    fn parse_host_port_defaults_when_missing() {
+        assert_eq!(parse_host_port("pve.example.com"), ("pve.example.com".to_string(), DEFAULT_PORT));
+    }
+
+    #[test]
+    fn parse_host_port_defaults_on_non_numeric_port() {
+        assert_eq!(
+            parse_host_port("pve.example.com:not-a-port"),
+            ("pve.example.com:not-a-port".to_string(), DEFAULT_PORT)
+        );
+    }
+
+    #[test]
+    fn parse_fingerprint_accepts_colon_separated_hex() {
+        let colon_separated = (0..32).map(|i| format!("{:02x}", i)).collect::<Vec<_>>().join(":");
+        let expected: [u8; 32] = std::array::from_fn(|i| i as u8);
+        assert_eq!(parse_fingerprint(&colon_separated).unwrap(), expected);
+    }
+
+    #[test]
+    fn parse_fingerprint_accepts_bare_hex() {
+        let bare = (0..32).map(|i| format!("{:02x}", i)).collect::<String>();
+        let expected: [u8; 32] = std::array::from_fn(|i| i as u8);
+        assert_eq!(parse_fingerprint(&bare).unwrap(), expected);
+    }
+
+    #[test]
+    fn parse_fingerprint_rejects_short_input() {
+        assert!(parse_fingerprint("aa:bb:cc").is_err());
+    }
+
+    #[test]
+    fn parse_fingerprint_rejects_non_hex() {
+        let invalid = "zz".repeat(32);
+        assert!(parse_fingerprint(&invalid).is_err());
+    }
+
+    #[test]
+    fn parse_upid_node_extracts_node_name() {
+        let upid = "UPID:pve1:00001234:0005678A:0065A1B2:vzstart:100:root@pam:";
+        assert_eq!(parse_upid_node(upid).unwrap(), "pve1");
+    }
+
+    #[test]
+    fn parse_upid_node_rejects_malformed_upid() {
+        assert!(parse_upid_node("not-a-upid").is_err());
+    }
+
+    #[test]
+    fn parse_upid_node_rejects_empty_node_segment() {
+        assert!(parse_upid_node("UPID::00001234:0005678A:0065A1B2:vzstart:100:root@pam:").is_err());
     }
 }